@@ -0,0 +1,127 @@
+//! The conversational agent: resolves a model name to one of the
+//! configured [`Client`] backends and drives a single conversation through
+//! it, tracking history so the HTTP layer can persist and resume sessions.
+
+use anyhow::{bail, Context, Result};
+use futures::stream::BoxStream;
+use serde::Serialize;
+use tokio_util::sync::CancellationToken;
+
+use crate::client::{self, Client, ClientConfig};
+use crate::config::Config;
+use crate::memory::MemoryManager;
+
+/// One exchange turn, shared with [`crate::server::session`] so session
+/// history round-trips through the agent unchanged.
+#[derive(Debug, Clone, Serialize)]
+pub struct Turn {
+    pub role: String,
+    pub content: String,
+}
+
+/// Per-request generation parameters, derived from [`crate::config::AgentSection`]
+/// and any request-level override of the model.
+pub struct AgentConfig {
+    pub model: String,
+    pub context_window: usize,
+    pub reserve_tokens: usize,
+}
+
+pub struct Agent {
+    model: String,
+    client: Box<dyn Client>,
+    #[allow(dead_code)]
+    memory: MemoryManager,
+    history: Vec<Turn>,
+}
+
+impl Agent {
+    pub async fn new(agent_config: AgentConfig, config: &Config, memory: MemoryManager) -> Result<Self> {
+        let client = resolve_client(&config.agent.clients, &agent_config.model)?;
+        Ok(Self {
+            model: agent_config.model,
+            client,
+            memory,
+            history: Vec::new(),
+        })
+    }
+
+    pub fn model(&self) -> &str {
+        &self.model
+    }
+
+    /// Start a conversation with empty history.
+    pub async fn new_session(&mut self) -> Result<()> {
+        self.history.clear();
+        Ok(())
+    }
+
+    /// Resume a conversation from previously persisted history.
+    pub async fn resume_session(&mut self, history: &[Turn]) -> Result<()> {
+        self.history = history.to_vec();
+        Ok(())
+    }
+
+    pub async fn chat(&mut self, message: &str) -> Result<String> {
+        let prompt = self.prompt_with_history(message);
+        let response = self.client.send(&self.model, &prompt).await?;
+        self.record_turn(message, &response);
+        Ok(response)
+    }
+
+    /// Like [`Self::chat`], but abandons generation and returns an error as
+    /// soon as `shutdown` is cancelled, instead of letting it run to
+    /// completion regardless of a server shutting down underneath it.
+    pub async fn chat_cancellable(
+        &mut self,
+        message: &str,
+        shutdown: CancellationToken,
+    ) -> Result<String> {
+        let prompt = self.prompt_with_history(message);
+        let response = tokio::select! {
+            result = self.client.send(&self.model, &prompt) => result?,
+            _ = shutdown.cancelled() => bail!("cancelled: server is shutting down"),
+        };
+        self.record_turn(message, &response);
+        Ok(response)
+    }
+
+    /// Stream the response to `message` as incremental deltas.
+    pub async fn chat_stream(&self, message: &str) -> Result<BoxStream<'static, Result<String>>> {
+        let prompt = self.prompt_with_history(message);
+        self.client.send_streaming(&self.model, &prompt).await
+    }
+
+    fn record_turn(&mut self, message: &str, response: &str) {
+        self.history.push(Turn {
+            role: "user".to_string(),
+            content: message.to_string(),
+        });
+        self.history.push(Turn {
+            role: "assistant".to_string(),
+            content: response.to_string(),
+        });
+    }
+
+    fn prompt_with_history(&self, message: &str) -> String {
+        let mut prompt = String::new();
+        for turn in &self.history {
+            prompt.push_str(&format!("{}: {}\n", turn.role, turn.content));
+        }
+        prompt.push_str(&format!("user: {message}\n"));
+        prompt
+    }
+}
+
+/// Pick the client configured to serve `model`, falling back to the first
+/// configured client when no client explicitly lists it (the common case
+/// for a single-backend setup).
+fn resolve_client(clients: &[ClientConfig], model: &str) -> Result<Box<dyn Client>> {
+    let config = clients
+        .iter()
+        .find(|c| client::configured_models(c).iter().any(|m| m == model))
+        .or_else(|| clients.first())
+        .with_context(|| format!("no LLM client configured to serve model '{model}'"))?;
+
+    client::init(config)
+}
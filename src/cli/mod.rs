@@ -3,6 +3,7 @@ pub mod chat;
 pub mod config;
 pub mod daemon;
 pub mod memory;
+pub mod models;
 
 use clap::{Parser, Subcommand};
 
@@ -39,4 +40,7 @@ pub enum Commands {
 
     /// Configuration management
     Config(config::ConfigArgs),
+
+    /// List configured clients and the models they advertise
+    Models(models::ModelsArgs),
 }
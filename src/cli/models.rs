@@ -0,0 +1,48 @@
+use anyhow::Result;
+use clap::Args;
+
+use localgpt::client;
+use localgpt::config::Config;
+
+#[derive(Args)]
+pub struct ModelsArgs {
+    /// Output format: table (default) or json
+    #[arg(short, long, default_value = "table")]
+    pub format: String,
+}
+
+pub async fn run(args: ModelsArgs) -> Result<()> {
+    let config = Config::load()?;
+
+    let mut models = Vec::new();
+    for client_config in &config.agent.clients {
+        models.extend(client::discover_models(client_config).await?);
+    }
+
+    match args.format.as_str() {
+        "json" => {
+            let json = serde_json::to_string_pretty(&models)?;
+            println!("{}", json);
+        }
+        _ => {
+            if models.is_empty() {
+                println!("No models found. Check your configured clients with `localgpt config show`.");
+                return Ok(());
+            }
+
+            println!("{:<30} {:<20} CONTEXT WINDOW", "MODEL", "CLIENT");
+            for model in &models {
+                let context_window = model
+                    .context_window
+                    .map(|w| w.to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                println!(
+                    "{:<30} {:<20} {}",
+                    model.name, model.client_type, context_window
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+//! Network tuning shared by every backend client: proxying, connect
+//! timeouts, and an overridable base URL, all nested under an `extra`
+//! block in that client's config.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HttpExtra {
+    /// `https://` or `socks5://` proxy URL. Falls back to `HTTPS_PROXY` /
+    /// `ALL_PROXY` env vars when unset.
+    #[serde(default)]
+    pub proxy: Option<String>,
+
+    /// Seconds to allow for establishing the TCP/TLS connection.
+    #[serde(default)]
+    pub connect_timeout: Option<u64>,
+
+    /// Overrides the client's configured base URL, for pointing at a
+    /// mirror or a differently-versioned endpoint without duplicating the
+    /// rest of the client's config.
+    #[serde(default)]
+    pub api_base: Option<String>,
+}
+
+impl HttpExtra {
+    /// Resolve the base URL to use: `extra.api_base` if set, else `default_base`.
+    pub fn resolve_api_base<'a>(&'a self, default_base: &'a str) -> &'a str {
+        self.api_base.as_deref().unwrap_or(default_base)
+    }
+
+    /// Build a `reqwest::Client` honoring this config's proxy and connect
+    /// timeout settings.
+    pub fn build_http_client(&self) -> Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder();
+
+        if let Some(proxy_url) = self
+            .proxy
+            .clone()
+            .or_else(|| std::env::var("HTTPS_PROXY").ok())
+            .or_else(|| std::env::var("ALL_PROXY").ok())
+        {
+            let proxy = reqwest::Proxy::all(&proxy_url)
+                .with_context(|| format!("invalid proxy URL: {proxy_url}"))?;
+            builder = builder.proxy(proxy);
+        }
+
+        if let Some(seconds) = self.connect_timeout {
+            builder = builder.connect_timeout(Duration::from_secs(seconds));
+        }
+
+        Ok(builder.build()?)
+    }
+}
@@ -0,0 +1,82 @@
+//! Client for `llama.cpp`'s built-in HTTP server (`llama-server`), which
+//! speaks the same OpenAI-compatible `/v1/chat/completions` surface but
+//! never requires an API key and typically serves a single local model.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use serde::{Deserialize, Serialize};
+
+use super::{
+    openai_compatible::{self, OpenAiCompatibleConfig},
+    Client, HttpExtra, ModelInfo, OpenAiCompatibleClient,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlamaCppConfig {
+    /// Base URL of the llama.cpp server, e.g. `http://localhost:8080/v1`.
+    #[serde(default = "default_api_base")]
+    pub api_base: String,
+
+    /// Models this client is allowed to serve.
+    #[serde(default)]
+    pub models: Vec<String>,
+
+    /// Proxy, connect timeout, and base-URL override.
+    #[serde(default)]
+    pub extra: HttpExtra,
+}
+
+fn default_api_base() -> String {
+    "http://localhost:8080/v1".to_string()
+}
+
+pub struct LlamaCppClient {
+    inner: OpenAiCompatibleClient,
+}
+
+impl LlamaCppClient {
+    pub fn new(config: LlamaCppConfig) -> Result<Self> {
+        let inner = OpenAiCompatibleClient::new(OpenAiCompatibleConfig {
+            api_base: config.api_base,
+            api_key: None,
+            models: config.models,
+            extra: config.extra,
+        })?;
+        Ok(Self { inner })
+    }
+}
+
+#[async_trait]
+impl Client for LlamaCppClient {
+    async fn send(&self, model: &str, prompt: &str) -> Result<String> {
+        self.inner.send(model, prompt).await
+    }
+
+    async fn send_streaming(
+        &self,
+        model: &str,
+        prompt: &str,
+    ) -> Result<BoxStream<'static, Result<String>>> {
+        self.inner.send_streaming(model, prompt).await
+    }
+
+    fn models(&self) -> &[String] {
+        self.inner.models()
+    }
+}
+
+/// Lists models via llama.cpp's OpenAI-compatible `/v1/models`, falling back
+/// to the configured `models` list if the server can't be reached.
+pub async fn discover_models(config: &LlamaCppConfig) -> Result<Vec<ModelInfo>> {
+    openai_compatible::discover_models_as(
+        &OpenAiCompatibleConfig {
+            api_base: config.api_base.clone(),
+            api_key: None,
+            models: config.models.clone(),
+            extra: config.extra.clone(),
+        },
+        "llamacpp",
+    )
+    .await
+}
@@ -0,0 +1,159 @@
+//! Pluggable LLM backend clients.
+//!
+//! Each backend lives in its own module and implements [`Client`]. The
+//! config file tags a client with a `type` field, deserialized into
+//! [`ClientConfig`]; [`init`] builds the concrete client for a given
+//! variant. New backends are wired in with [`register_clients!`] so the
+//! enum and dispatcher stay in one place.
+
+mod extra;
+mod llamacpp;
+mod ollama;
+mod openai_compatible;
+
+pub use extra::HttpExtra;
+pub use llamacpp::{LlamaCppClient, LlamaCppConfig};
+pub use ollama::{OllamaClient, OllamaConfig};
+pub use openai_compatible::{OpenAiCompatibleClient, OpenAiCompatibleConfig};
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use serde::{Deserialize, Serialize};
+
+/// A backend capable of sending chat completions to an LLM.
+#[async_trait]
+pub trait Client: Send + Sync {
+    /// Send a prompt to `model` and wait for the full response.
+    async fn send(&self, model: &str, prompt: &str) -> Result<String>;
+
+    /// Send a prompt to `model`, streaming back incremental deltas.
+    async fn send_streaming(
+        &self,
+        model: &str,
+        prompt: &str,
+    ) -> Result<BoxStream<'static, Result<String>>>;
+
+    /// Model names this client is configured to serve.
+    fn models(&self) -> &[String];
+}
+
+/// Declares the tagged `ClientConfig` enum and the `init` dispatcher for a
+/// set of backend modules, so adding a backend only means one macro line
+/// plus its module.
+macro_rules! register_clients {
+    ($($variant:ident => $config:ty, $client:ty);+ $(;)?) => {
+        /// Tagged client configuration, one variant per backend module.
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[serde(tag = "type", rename_all = "snake_case")]
+        pub enum ClientConfig {
+            $($variant($config)),+
+        }
+
+        /// Build the concrete [`Client`] for a [`ClientConfig`].
+        pub fn init(config: &ClientConfig) -> Result<Box<dyn Client>> {
+            match config {
+                $(ClientConfig::$variant(cfg) => {
+                    Ok(Box::new(<$client>::new(cfg.clone())?) as Box<dyn Client>)
+                }),+
+            }
+        }
+    };
+}
+
+register_clients! {
+    Ollama => OllamaConfig, OllamaClient;
+    OpenaiCompatible => OpenAiCompatibleConfig, OpenAiCompatibleClient;
+    Llamacpp => LlamaCppConfig, LlamaCppClient;
+}
+
+/// The model names a client is configured to serve, without constructing
+/// the client itself (cheap enough to call while resolving which backend
+/// a given model name belongs to).
+pub fn configured_models(config: &ClientConfig) -> &[String] {
+    match config {
+        ClientConfig::Ollama(cfg) => &cfg.models,
+        ClientConfig::OpenaiCompatible(cfg) => &cfg.models,
+        ClientConfig::Llamacpp(cfg) => &cfg.models,
+    }
+}
+
+/// The `extra: HttpExtra` block common to every backend variant.
+fn extra(config: &ClientConfig) -> &HttpExtra {
+    match config {
+        ClientConfig::Ollama(cfg) => &cfg.extra,
+        ClientConfig::OpenaiCompatible(cfg) => &cfg.extra,
+        ClientConfig::Llamacpp(cfg) => &cfg.extra,
+    }
+}
+
+fn extra_mut(config: &mut ClientConfig) -> &mut HttpExtra {
+    match config {
+        ClientConfig::Ollama(cfg) => &mut cfg.extra,
+        ClientConfig::OpenaiCompatible(cfg) => &mut cfg.extra,
+        ClientConfig::Llamacpp(cfg) => &mut cfg.extra,
+    }
+}
+
+/// Get `extra.<field>`, as used by `config get agent.clients.<index>.extra.<field>`.
+pub fn get_extra_value(config: &ClientConfig, field: &str) -> Result<String> {
+    let extra = extra(config);
+    match field {
+        "proxy" => extra.proxy.clone().context("proxy is not set"),
+        "connect_timeout" => extra
+            .connect_timeout
+            .map(|secs| secs.to_string())
+            .context("connect_timeout is not set"),
+        "api_base" => extra.api_base.clone().context("api_base override is not set"),
+        _ => bail!("unknown client config key: extra.{field}"),
+    }
+}
+
+/// Set `extra.<field>`, as used by `config set agent.clients.<index>.extra.<field>`.
+pub fn set_extra_value(config: &mut ClientConfig, field: &str, value: &str) -> Result<()> {
+    let extra = extra_mut(config);
+    match field {
+        "proxy" => extra.proxy = Some(value.to_string()),
+        "connect_timeout" => {
+            extra.connect_timeout = Some(value.parse().context("invalid connect_timeout")?)
+        }
+        "api_base" => extra.api_base = Some(value.to_string()),
+        _ => bail!("unknown client config key: extra.{field}"),
+    }
+    Ok(())
+}
+
+/// A model advertised by a configured client, for `/api/models` and
+/// `localgpt models`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelInfo {
+    pub name: String,
+    pub client_type: &'static str,
+    pub context_window: Option<u64>,
+}
+
+/// This client's type tag as used in `ClientConfig`'s `type` field.
+pub fn client_type(config: &ClientConfig) -> &'static str {
+    match config {
+        ClientConfig::Ollama(_) => "ollama",
+        ClientConfig::OpenaiCompatible(_) => "openai_compatible",
+        ClientConfig::Llamacpp(_) => "llamacpp",
+    }
+}
+
+/// List the models a client advertises, querying its live introspection
+/// endpoint when the backend has one and falling back to the configured
+/// `models` list otherwise. Each backend's own guess at `client_type` is
+/// overwritten with [`client_type`]'s so the two can't drift apart.
+pub async fn discover_models(config: &ClientConfig) -> Result<Vec<ModelInfo>> {
+    let mut models = match config {
+        ClientConfig::Ollama(cfg) => ollama::discover_models(cfg).await?,
+        ClientConfig::OpenaiCompatible(cfg) => openai_compatible::discover_models(cfg).await?,
+        ClientConfig::Llamacpp(cfg) => llamacpp::discover_models(cfg).await?,
+    };
+    let tag = client_type(config);
+    for model in &mut models {
+        model.client_type = tag;
+    }
+    Ok(models)
+}
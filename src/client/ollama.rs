@@ -0,0 +1,261 @@
+//! Ollama backend client.
+//!
+//! Talks to a local (or remote) Ollama instance's `/api/generate` endpoint.
+//! Ollama's streaming response is newline-delimited JSON, each line holding
+//! a partial `response` field and a final line with `done: true`.
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::pin::Pin;
+
+use super::{Client, HttpExtra, ModelInfo};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OllamaConfig {
+    /// Base URL of the Ollama server, e.g. `http://localhost:11434`.
+    #[serde(default = "default_api_base")]
+    pub api_base: String,
+
+    /// Models this client is allowed to serve.
+    #[serde(default)]
+    pub models: Vec<String>,
+
+    /// Proxy, connect timeout, and base-URL override.
+    #[serde(default)]
+    pub extra: HttpExtra,
+}
+
+fn default_api_base() -> String {
+    "http://localhost:11434".to_string()
+}
+
+#[derive(Debug, Deserialize)]
+struct GenerateChunk {
+    response: String,
+    #[serde(default)]
+    done: bool,
+}
+
+pub struct OllamaClient {
+    config: OllamaConfig,
+    http: reqwest::Client,
+}
+
+impl OllamaClient {
+    pub fn new(config: OllamaConfig) -> Result<Self> {
+        let http = config.extra.build_http_client()?;
+        Ok(Self { config, http })
+    }
+}
+
+#[async_trait]
+impl Client for OllamaClient {
+    async fn send(&self, model: &str, prompt: &str) -> Result<String> {
+        let mut deltas = self.send_streaming(model, prompt).await?;
+        let mut text = String::new();
+        while let Some(delta) = deltas.next().await {
+            text.push_str(&delta?);
+        }
+        Ok(text)
+    }
+
+    async fn send_streaming(
+        &self,
+        model: &str,
+        prompt: &str,
+    ) -> Result<BoxStream<'static, Result<String>>> {
+        let response = self
+            .http
+            .post(format!(
+                "{}/api/generate",
+                self.config.extra.resolve_api_base(&self.config.api_base)
+            ))
+            .json(&serde_json::json!({
+                "model": model,
+                "prompt": prompt,
+                "stream": true,
+            }))
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let bytes_stream = response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(anyhow::Error::from));
+
+        Ok(buffered_deltas(bytes_stream))
+    }
+
+    fn models(&self) -> &[String] {
+        &self.config.models
+    }
+}
+
+struct BufferState<S> {
+    stream: Pin<Box<S>>,
+    buffer: String,
+    pending: VecDeque<Result<String>>,
+    done: bool,
+}
+
+/// Ollama's NDJSON lines aren't guaranteed to land on `bytes_stream()` chunk
+/// boundaries, so a trailing partial line from one chunk is carried over and
+/// completed by the next. Unlike a plain `scan`, this also flushes whatever
+/// is left in the buffer once the upstream stream ends, in case the final
+/// line wasn't newline-terminated.
+fn buffered_deltas(
+    bytes_stream: impl Stream<Item = Result<bytes::Bytes>> + Send + 'static,
+) -> BoxStream<'static, Result<String>> {
+    let state = BufferState {
+        stream: Box::pin(bytes_stream),
+        buffer: String::new(),
+        pending: VecDeque::new(),
+        done: false,
+    };
+
+    let lines = stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(line) = state.pending.pop_front() {
+                return Some((line, state));
+            }
+            if state.done {
+                return None;
+            }
+            match state.stream.next().await {
+                Some(Ok(bytes)) => {
+                    state.buffer.push_str(&String::from_utf8_lossy(&bytes));
+                    while let Some(pos) = state.buffer.find('\n') {
+                        let line = state.buffer[..pos].trim().to_string();
+                        state.buffer.drain(..=pos);
+                        if !line.is_empty() {
+                            state.pending.push_back(parse_line(&line));
+                        }
+                    }
+                }
+                Some(Err(e)) => {
+                    state.done = true;
+                    state.pending.push_back(Err(e));
+                }
+                None => {
+                    state.done = true;
+                    let trailing = state.buffer.trim().to_string();
+                    if !trailing.is_empty() {
+                        state.pending.push_back(parse_line(&trailing));
+                    }
+                }
+            }
+        }
+    });
+
+    let deltas = lines.filter_map(|result| async move {
+        match result {
+            Ok(text) if text.is_empty() => None,
+            other => Some(other),
+        }
+    });
+
+    Box::pin(deltas)
+}
+
+/// Lists locally pulled models via Ollama's `/api/tags`, falling back to the
+/// configured `models` list if the server can't be reached. `/api/tags`
+/// doesn't carry context length, so each model is cross-referenced against
+/// `/api/show` to fill in [`ModelInfo::context_window`].
+pub async fn discover_models(config: &OllamaConfig) -> Result<Vec<ModelInfo>> {
+    let http = config.extra.build_http_client()?;
+    let base = config.extra.resolve_api_base(&config.api_base);
+
+    let tags: Option<Value> = http
+        .get(format!("{base}/api/tags"))
+        .send()
+        .await
+        .ok()
+        .filter(|res| res.status().is_success());
+    let tags = match tags {
+        Some(response) => response.json().await.ok(),
+        None => None,
+    };
+
+    if let Some(body) = tags {
+        if let Some(models) = body["models"].as_array() {
+            let mut infos = Vec::with_capacity(models.len());
+            for name in models.iter().filter_map(|m| m["name"].as_str()) {
+                infos.push(ModelInfo {
+                    name: name.to_string(),
+                    client_type: "ollama",
+                    context_window: show_context_length(&http, base, name).await,
+                });
+            }
+            return Ok(infos);
+        }
+    }
+
+    Ok(config
+        .models
+        .iter()
+        .map(|name| ModelInfo {
+            name: name.clone(),
+            client_type: "ollama",
+            context_window: None,
+        })
+        .collect())
+}
+
+/// Looks up a model's context length via `/api/show`, which nests it under a
+/// key like `llama.context_length` whose prefix varies by architecture.
+async fn show_context_length(http: &reqwest::Client, base: &str, model: &str) -> Option<u64> {
+    let body: Value = http
+        .post(format!("{base}/api/show"))
+        .json(&serde_json::json!({ "model": model }))
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+
+    body["model_info"]
+        .as_object()?
+        .iter()
+        .find(|(key, _)| key.ends_with("context_length"))
+        .and_then(|(_, value)| value.as_u64())
+}
+
+fn parse_line(line: &str) -> Result<String> {
+    let value: Value = serde_json::from_str(line)?;
+    let chunk: GenerateChunk = serde_json::from_value(value)?;
+    if chunk.done {
+        Ok(String::new())
+    } else {
+        Ok(chunk.response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn collect(chunks: Vec<&'static str>) -> Vec<String> {
+        let bytes_stream = stream::iter(chunks.into_iter().map(|c| Ok(bytes::Bytes::from(c))));
+        buffered_deltas(bytes_stream)
+            .map(|delta| delta.unwrap())
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn reassembles_a_line_split_across_chunks() {
+        let deltas = collect(vec!["{\"respon", "se\":\"hi\",\"done\":false}\n"]).await;
+        assert_eq!(deltas, vec!["hi"]);
+    }
+
+    #[tokio::test]
+    async fn flushes_a_trailing_line_with_no_final_newline() {
+        let deltas = collect(vec!["{\"response\":\"hi\",\"done\":false}"]).await;
+        assert_eq!(deltas, vec!["hi"]);
+    }
+}
@@ -0,0 +1,260 @@
+//! Client for OpenAI-compatible chat completion endpoints (OpenAI itself,
+//! or any server implementing the same `/v1/chat/completions` surface).
+
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use futures::stream::{self, BoxStream, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::pin::Pin;
+
+use super::{Client, HttpExtra, ModelInfo};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAiCompatibleConfig {
+    /// Base URL of the API, e.g. `https://api.openai.com/v1`.
+    pub api_base: String,
+
+    /// API key sent as a `Bearer` token, if the endpoint requires one.
+    #[serde(default)]
+    pub api_key: Option<String>,
+
+    /// Models this client is allowed to serve.
+    #[serde(default)]
+    pub models: Vec<String>,
+
+    /// Proxy, connect timeout, and base-URL override.
+    #[serde(default)]
+    pub extra: HttpExtra,
+}
+
+pub struct OpenAiCompatibleClient {
+    config: OpenAiCompatibleConfig,
+    http: reqwest::Client,
+}
+
+impl OpenAiCompatibleClient {
+    pub fn new(config: OpenAiCompatibleConfig) -> Result<Self> {
+        let http = config.extra.build_http_client()?;
+        Ok(Self { config, http })
+    }
+
+    fn request(&self, model: &str, prompt: &str, stream: bool) -> reqwest::RequestBuilder {
+        let mut req = self
+            .http
+            .post(format!(
+                "{}/chat/completions",
+                self.config.extra.resolve_api_base(&self.config.api_base)
+            ))
+            .json(&serde_json::json!({
+                "model": model,
+                "messages": [{"role": "user", "content": prompt}],
+                "stream": stream,
+            }));
+
+        if let Some(api_key) = &self.config.api_key {
+            req = req.bearer_auth(api_key);
+        }
+
+        req
+    }
+}
+
+#[async_trait]
+impl Client for OpenAiCompatibleClient {
+    async fn send(&self, model: &str, prompt: &str) -> Result<String> {
+        let response: serde_json::Value = self
+            .request(model, prompt, false)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        response["choices"][0]["message"]["content"]
+            .as_str()
+            .map(str::to_string)
+            .context("OpenAI-compatible response missing choices[0].message.content")
+    }
+
+    async fn send_streaming(
+        &self,
+        model: &str,
+        prompt: &str,
+    ) -> Result<BoxStream<'static, Result<String>>> {
+        let response = self
+            .request(model, prompt, true)
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let bytes_stream = response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(anyhow::Error::from));
+
+        Ok(buffered_deltas(bytes_stream))
+    }
+
+    fn models(&self) -> &[String] {
+        &self.config.models
+    }
+}
+
+struct BufferState<S> {
+    stream: Pin<Box<S>>,
+    buffer: String,
+    pending: VecDeque<Result<String>>,
+    done: bool,
+}
+
+/// SSE `data:` lines aren't guaranteed to land on `bytes_stream()` chunk
+/// boundaries, so a trailing partial line from one chunk is carried over and
+/// completed by the next. Unlike a plain `scan`, this also flushes whatever
+/// is left in the buffer once the upstream stream ends, in case the final
+/// line wasn't newline-terminated.
+fn buffered_deltas(
+    bytes_stream: impl Stream<Item = Result<bytes::Bytes>> + Send + 'static,
+) -> BoxStream<'static, Result<String>> {
+    let state = BufferState {
+        stream: Box::pin(bytes_stream),
+        buffer: String::new(),
+        pending: VecDeque::new(),
+        done: false,
+    };
+
+    let lines = stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(line) = state.pending.pop_front() {
+                return Some((line, state));
+            }
+            if state.done {
+                return None;
+            }
+            match state.stream.next().await {
+                Some(Ok(bytes)) => {
+                    state.buffer.push_str(&String::from_utf8_lossy(&bytes));
+                    while let Some(pos) = state.buffer.find('\n') {
+                        let line = state.buffer[..pos].trim().to_string();
+                        state.buffer.drain(..=pos);
+                        if let Some(data) = line.strip_prefix("data: ") {
+                            if data != "[DONE]" {
+                                state.pending.push_back(parse_delta(data));
+                            }
+                        }
+                    }
+                }
+                Some(Err(e)) => {
+                    state.done = true;
+                    state.pending.push_back(Err(e));
+                }
+                None => {
+                    state.done = true;
+                    let trailing = state.buffer.trim().to_string();
+                    if let Some(data) = trailing.strip_prefix("data: ") {
+                        if data != "[DONE]" {
+                            state.pending.push_back(parse_delta(data));
+                        }
+                    }
+                }
+            }
+        }
+    });
+
+    let deltas = lines.filter_map(|result| async move {
+        match result {
+            Ok(text) if text.is_empty() => None,
+            other => Some(other),
+        }
+    });
+
+    Box::pin(deltas)
+}
+
+/// Lists models via an OpenAI-compatible `/models` endpoint, falling back to
+/// the configured `models` list if the server can't be reached. Shared by
+/// both the `openai_compatible` and `llamacpp` backends; `client_type` is
+/// only used to label the returned [`ModelInfo`]s.
+pub async fn discover_models_as(
+    config: &OpenAiCompatibleConfig,
+    client_type: &'static str,
+) -> Result<Vec<ModelInfo>> {
+    let http = config.extra.build_http_client()?;
+    let url = format!(
+        "{}/models",
+        config.extra.resolve_api_base(&config.api_base)
+    );
+    let mut req = http.get(url);
+    if let Some(api_key) = &config.api_key {
+        req = req.bearer_auth(api_key);
+    }
+
+    let body: Option<serde_json::Value> = match req.send().await {
+        Ok(response) if response.status().is_success() => response.json().await.ok(),
+        _ => None,
+    };
+
+    if let Some(body) = body {
+        if let Some(models) = body["data"].as_array() {
+            return Ok(models
+                .iter()
+                .filter_map(|m| m["id"].as_str())
+                .map(|name| ModelInfo {
+                    name: name.to_string(),
+                    client_type,
+                    context_window: None,
+                })
+                .collect());
+        }
+    }
+
+    Ok(config
+        .models
+        .iter()
+        .map(|name| ModelInfo {
+            name: name.clone(),
+            client_type,
+            context_window: None,
+        })
+        .collect())
+}
+
+pub async fn discover_models(config: &OpenAiCompatibleConfig) -> Result<Vec<ModelInfo>> {
+    discover_models_as(config, "openai_compatible").await
+}
+
+fn parse_delta(data: &str) -> Result<String> {
+    let value: serde_json::Value = serde_json::from_str(data)?;
+    Ok(value["choices"][0]["delta"]["content"]
+        .as_str()
+        .unwrap_or_default()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn collect(chunks: Vec<&'static str>) -> Vec<String> {
+        let bytes_stream = stream::iter(chunks.into_iter().map(|c| Ok(bytes::Bytes::from(c))));
+        buffered_deltas(bytes_stream)
+            .map(|delta| delta.unwrap())
+            .collect()
+            .await
+    }
+
+    #[tokio::test]
+    async fn reassembles_a_line_split_across_chunks() {
+        let deltas = collect(vec![
+            "data: {\"choices\":[{\"delta\":{\"conte",
+            "nt\":\"hi\"}}]}\n",
+        ])
+        .await;
+        assert_eq!(deltas, vec!["hi"]);
+    }
+
+    #[tokio::test]
+    async fn flushes_a_trailing_line_with_no_final_newline() {
+        let deltas = collect(vec!["data: {\"choices\":[{\"delta\":{\"content\":\"hi\"}}]}"]).await;
+        assert_eq!(deltas, vec!["hi"]);
+    }
+}
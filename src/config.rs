@@ -0,0 +1,200 @@
+//! Application configuration, loaded from and saved to a TOML file.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::client::{self, ClientConfig};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Config {
+    #[serde(default)]
+    pub server: ServerConfig,
+    #[serde(default)]
+    pub agent: AgentSection,
+    #[serde(default)]
+    pub memory: MemoryConfig,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerConfig {
+    #[serde(default = "default_bind")]
+    pub bind: String,
+    #[serde(default = "default_port")]
+    pub port: u16,
+}
+
+fn default_bind() -> String {
+    "127.0.0.1".to_string()
+}
+
+fn default_port() -> u16 {
+    8080
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind: default_bind(),
+            port: default_port(),
+        }
+    }
+}
+
+/// The `[agent]` section: generation defaults plus the pool of LLM backend
+/// clients a model name is resolved against. Not to be confused with
+/// [`crate::agent::AgentConfig`], the per-request params derived from this.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AgentSection {
+    #[serde(default = "default_model")]
+    pub default_model: String,
+    #[serde(default = "default_context_window")]
+    pub context_window: usize,
+    #[serde(default = "default_reserve_tokens")]
+    pub reserve_tokens: usize,
+    #[serde(default)]
+    pub clients: Vec<ClientConfig>,
+}
+
+fn default_model() -> String {
+    "llama3".to_string()
+}
+
+fn default_context_window() -> usize {
+    8192
+}
+
+fn default_reserve_tokens() -> usize {
+    512
+}
+
+impl Default for AgentSection {
+    fn default() -> Self {
+        Self {
+            default_model: default_model(),
+            context_window: default_context_window(),
+            reserve_tokens: default_reserve_tokens(),
+            clients: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MemoryConfig {
+    #[serde(default = "default_workspace")]
+    pub workspace: String,
+    #[serde(default = "default_index_path")]
+    pub index_path: PathBuf,
+}
+
+fn default_workspace() -> String {
+    ".".to_string()
+}
+
+fn default_index_path() -> PathBuf {
+    "memory.sqlite3".into()
+}
+
+impl Default for MemoryConfig {
+    fn default() -> Self {
+        Self {
+            workspace: default_workspace(),
+            index_path: default_index_path(),
+        }
+    }
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            server: ServerConfig::default(),
+            agent: AgentSection::default(),
+            memory: MemoryConfig::default(),
+        }
+    }
+}
+
+impl Config {
+    pub fn config_path() -> Result<PathBuf> {
+        let dir = dirs::config_dir().context("could not determine config directory")?;
+        Ok(dir.join("localgpt").join("config.toml"))
+    }
+
+    pub fn load() -> Result<Self> {
+        let path = Self::config_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = std::fs::read_to_string(&path)?;
+        Ok(toml::from_str(&contents)?)
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Self::config_path()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Look up a value by dotted key path, e.g. `agent.default_model`.
+    pub fn get_value(&self, key: &str) -> Result<String> {
+        let parts: Vec<&str> = key.split('.').collect();
+        match parts.as_slice() {
+            ["server", "bind"] => Ok(self.server.bind.clone()),
+            ["server", "port"] => Ok(self.server.port.to_string()),
+            ["agent", "default_model"] => Ok(self.agent.default_model.clone()),
+            ["agent", "context_window"] => Ok(self.agent.context_window.to_string()),
+            ["agent", "reserve_tokens"] => Ok(self.agent.reserve_tokens.to_string()),
+            ["agent", "clients", idx, "extra", field] => {
+                client::get_extra_value(self.client_at(idx)?, field)
+            }
+            ["memory", "workspace"] => Ok(self.memory.workspace.clone()),
+            ["memory", "index_path"] => Ok(self.memory.index_path.display().to_string()),
+            _ => bail!("unknown config key: {key}"),
+        }
+    }
+
+    /// Set a value by dotted key path, e.g. `agent.default_model`.
+    pub fn set_value(&mut self, key: &str, value: &str) -> Result<()> {
+        let parts: Vec<&str> = key.split('.').collect();
+        match parts.as_slice() {
+            ["server", "bind"] => self.server.bind = value.to_string(),
+            ["server", "port"] => self.server.port = value.parse().context("invalid port")?,
+            ["agent", "default_model"] => self.agent.default_model = value.to_string(),
+            ["agent", "context_window"] => {
+                self.agent.context_window = value.parse().context("invalid context_window")?
+            }
+            ["agent", "reserve_tokens"] => {
+                self.agent.reserve_tokens = value.parse().context("invalid reserve_tokens")?
+            }
+            ["agent", "clients", idx, "extra", field] => {
+                let idx = *idx;
+                let field = *field;
+                client::set_extra_value(self.client_at_mut(idx)?, field, value)?
+            }
+            ["memory", "workspace"] => self.memory.workspace = value.to_string(),
+            ["memory", "index_path"] => self.memory.index_path = value.into(),
+            _ => bail!("unknown config key: {key}"),
+        }
+        Ok(())
+    }
+
+    /// Resolve `agent.clients.<index>` to the client at that position.
+    fn client_at(&self, index: &str) -> Result<&ClientConfig> {
+        let index: usize = index.parse().with_context(|| format!("invalid client index: {index}"))?;
+        self.agent
+            .clients
+            .get(index)
+            .with_context(|| format!("no client configured at index {index}"))
+    }
+
+    fn client_at_mut(&mut self, index: &str) -> Result<&mut ClientConfig> {
+        let index: usize = index.parse().with_context(|| format!("invalid client index: {index}"))?;
+        self.agent
+            .clients
+            .get_mut(index)
+            .with_context(|| format!("no client configured at index {index}"))
+    }
+}
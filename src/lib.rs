@@ -0,0 +1,8 @@
+//! LocalGPT library crate: config, agent, memory, and the HTTP server.
+//! `src/main.rs` is a thin CLI binary built on top of this.
+
+pub mod agent;
+pub mod client;
+pub mod config;
+pub mod memory;
+pub mod server;
@@ -24,5 +24,6 @@ async fn main() -> Result<()> {
         Commands::Daemon(args) => cli::daemon::run(args).await,
         Commands::Memory(args) => cli::memory::run(args).await,
         Commands::Config(args) => cli::config::run(args).await,
+        Commands::Models(args) => cli::models::run(args).await,
     }
 }
@@ -0,0 +1,99 @@
+//! Workspace memory index: a lightweight SQLite-backed store of file chunks
+//! used to ground the agent's answers in the user's codebase.
+
+use anyhow::Result;
+use rusqlite::{params, Connection};
+use std::sync::Mutex;
+
+use crate::config::MemoryConfig;
+
+pub struct SearchResult {
+    pub file: String,
+    pub line_start: i32,
+    pub line_end: i32,
+    pub content: String,
+    pub score: f64,
+}
+
+pub struct MemoryStats {
+    pub workspace: String,
+    pub total_files: usize,
+    pub total_chunks: usize,
+    pub index_size_kb: u64,
+}
+
+pub struct MemoryManager {
+    config: MemoryConfig,
+    conn: Mutex<Connection>,
+}
+
+impl MemoryManager {
+    pub fn new(config: &MemoryConfig) -> Result<Self> {
+        let conn = Connection::open(&config.index_path)?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS chunks (
+                id INTEGER PRIMARY KEY,
+                file TEXT NOT NULL,
+                line_start INTEGER NOT NULL,
+                line_end INTEGER NOT NULL,
+                content TEXT NOT NULL
+            );",
+        )?;
+        Ok(Self {
+            config: config.clone(),
+            conn: Mutex::new(conn),
+        })
+    }
+
+    pub fn chunk_count(&self) -> Result<usize> {
+        let conn = self.conn.lock().unwrap();
+        let count: i64 = conn.query_row("SELECT COUNT(*) FROM chunks", [], |row| row.get(0))?;
+        Ok(count as usize)
+    }
+
+    /// Naive substring search over indexed chunks, scored by match count.
+    pub fn search(&self, query: &str, limit: usize) -> Result<Vec<SearchResult>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT file, line_start, line_end, content FROM chunks WHERE content LIKE ?1",
+        )?;
+        let pattern = format!("%{query}%");
+
+        let mut results: Vec<SearchResult> = stmt
+            .query_map(params![pattern], |row| {
+                let content: String = row.get(3)?;
+                Ok(SearchResult {
+                    file: row.get(0)?,
+                    line_start: row.get(1)?,
+                    line_end: row.get(2)?,
+                    score: content.matches(query).count() as f64,
+                    content,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+
+        results.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap());
+        results.truncate(limit);
+        Ok(results)
+    }
+
+    pub fn stats(&self) -> Result<MemoryStats> {
+        let conn = self.conn.lock().unwrap();
+        let total_chunks: i64 = conn.query_row("SELECT COUNT(*) FROM chunks", [], |row| row.get(0))?;
+        let total_files: i64 = conn.query_row(
+            "SELECT COUNT(DISTINCT file) FROM chunks",
+            [],
+            |row| row.get(0),
+        )?;
+        let index_size_kb = std::fs::metadata(&self.config.index_path)
+            .map(|m| m.len() / 1024)
+            .unwrap_or(0);
+
+        Ok(MemoryStats {
+            workspace: self.config.workspace.clone(),
+            total_files: total_files as usize,
+            total_chunks: total_chunks as usize,
+            index_size_kb,
+        })
+    }
+}
@@ -1,33 +1,48 @@
 //! HTTP server for LocalGPT
 //!
-//! Note: The chat endpoint creates a new agent per request because the Agent
-//! struct contains SQLite connections that cannot be shared across threads.
-//! For persistent session state, use the CLI interface instead.
+//! Sessions persist across requests via [`SessionManager`]: the chat
+//! endpoint still spawns a fresh `Agent` per request, but conversation
+//! history for a `session_id` is loaded from and written back to the
+//! shared session store around it.
 
 use anyhow::Result;
 use axum::{
-    extract::{Query, State},
-    http::StatusCode,
-    response::{IntoResponse, Json, Response},
-    routing::{get, post},
+    extract::{Path, Query, State},
+    http::{HeaderValue, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Json, Response,
+    },
+    routing::{delete, get, post},
     Router,
 };
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use tokio_stream::wrappers::ReceiverStream;
+use tokio_util::sync::CancellationToken;
 use tower_http::cors::{Any, CorsLayer};
 use tracing::info;
+use uuid::Uuid;
 
-use crate::agent::{Agent, AgentConfig};
+use super::session::SessionManager;
+use crate::agent::{Agent, AgentConfig, Turn};
+use crate::client::{self, ModelInfo};
 use crate::config::Config;
 use crate::memory::MemoryManager;
 
+const SESSION_ID_HEADER: &str = "X-Session-Id";
+
 pub struct Server {
     config: Config,
 }
 
 struct AppState {
     config: Config,
+    sessions: Arc<SessionManager>,
+    shutdown: CancellationToken,
 }
 
 impl Server {
@@ -38,8 +53,15 @@ impl Server {
     }
 
     pub async fn run(&self) -> Result<()> {
+        let sessions_path = Config::config_path()?
+            .parent()
+            .map(|dir| dir.join("sessions.sqlite3"))
+            .unwrap_or_else(|| "sessions.sqlite3".into());
+        let shutdown = CancellationToken::new();
         let state = Arc::new(AppState {
             config: self.config.clone(),
+            sessions: Arc::new(SessionManager::new(sessions_path)?),
+            shutdown: shutdown.clone(),
         });
 
         let cors = CorsLayer::new()
@@ -50,9 +72,14 @@ impl Server {
         let app = Router::new()
             .route("/health", get(health_check))
             .route("/api/chat", post(chat))
+            .route("/api/chat/stream", post(chat_stream))
+            .route("/api/sessions", get(list_sessions))
+            .route("/api/sessions/:id", get(get_session))
+            .route("/api/sessions/:id", delete(delete_session))
             .route("/api/memory/search", get(memory_search))
             .route("/api/memory/stats", get(memory_stats))
             .route("/api/status", get(status))
+            .route("/api/models", get(models))
             .layer(cors)
             .with_state(state);
 
@@ -62,12 +89,43 @@ impl Server {
         info!("Starting HTTP server on http://{}", addr);
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
-        axum::serve(listener, app).await?;
+        axum::serve(listener, app)
+            .with_graceful_shutdown(shutdown_signal(shutdown))
+            .await?;
 
         Ok(())
     }
 }
 
+/// Resolves once Ctrl-C or SIGTERM is received, cancelling `token` so
+/// in-flight handlers can stop their generation early.
+async fn shutdown_signal(token: CancellationToken) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to install Ctrl-C handler");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to install SIGTERM handler")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+
+    info!("Shutting down, cancelling in-flight requests");
+    token.cancel();
+}
+
 // Error response type
 struct AppError(StatusCode, String);
 
@@ -100,26 +158,55 @@ async fn status(State(state): State<Arc<AppState>>) -> Json<StatusResponse> {
     })
 }
 
+// Model discovery endpoint
+#[derive(Serialize)]
+struct ModelsResponse {
+    models: Vec<ModelInfo>,
+}
+
+async fn models(State(state): State<Arc<AppState>>) -> Response {
+    match discover_all_models(&state.config).await {
+        Ok(models) => Json(ModelsResponse { models }).into_response(),
+        Err(e) => AppError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn discover_all_models(config: &Config) -> Result<Vec<ModelInfo>> {
+    let mut models = Vec::new();
+    for client_config in &config.agent.clients {
+        models.extend(client::discover_models(client_config).await?);
+    }
+    Ok(models)
+}
+
 // Chat endpoint
 #[derive(Deserialize)]
 struct ChatRequest {
     message: String,
     model: Option<String>,
+    session_id: Option<Uuid>,
 }
 
 #[derive(Serialize)]
 struct ChatResponse {
     response: String,
     model: String,
+    session_id: Uuid,
 }
 
 async fn chat(State(state): State<Arc<AppState>>, Json(request): Json<ChatRequest>) -> Response {
-    // Create a new agent for this request
-    // Note: This means no session persistence across HTTP requests
-    let result = tokio::task::spawn_blocking({
+    let session_id = match resolve_session(&state.sessions, request.session_id) {
+        Ok(id) => id,
+        Err(e) => return AppError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+
+    let shutdown = state.shutdown.clone();
+    let handle = tokio::task::spawn_blocking({
         let config = state.config.clone();
+        let sessions = state.sessions.clone();
         let message = request.message.clone();
         let model = request.model.clone();
+        let shutdown = shutdown.clone();
         move || {
             // Run in blocking context since Agent isn't Send+Sync
             let rt = tokio::runtime::Handle::current();
@@ -133,24 +220,178 @@ async fn chat(State(state): State<Arc<AppState>>, Json(request): Json<ChatReques
                 };
 
                 let mut agent = Agent::new(agent_config, &config, memory).await?;
-                agent.new_session().await?;
-
-                let response = agent.chat(&message).await?;
+                let history = sessions.history(session_id)?;
+                if history.is_empty() {
+                    agent.new_session().await?;
+                } else {
+                    agent.resume_session(&history).await?;
+                }
+
+                let response = agent.chat_cancellable(&message, shutdown).await?;
                 let model = agent.model().to_string();
 
-                Ok::<_, anyhow::Error>(ChatResponse { response, model })
+                sessions.append_exchange(session_id, &message, &response)?;
+
+                Ok::<_, anyhow::Error>(ChatResponse {
+                    response,
+                    model,
+                    session_id,
+                })
             })
         }
-    })
-    .await;
-
-    match result {
-        Ok(Ok(response)) => Json(response).into_response(),
+    });
+
+    // Await the blocking task itself rather than racing it against shutdown:
+    // dropping the JoinHandle wouldn't stop the underlying thread, it would
+    // just abandon it mid-generation while it keeps running (and could still
+    // append_exchange after we'd already told the client we were shutting
+    // down). agent.chat_cancellable already races generation against
+    // shutdown from inside the task, so by the time it resolves we know
+    // whether it was cancelled.
+    match handle.await {
+        Ok(Ok(response)) => {
+            let mut res = Json(response).into_response();
+            if let Ok(value) = HeaderValue::from_str(&session_id.to_string()) {
+                res.headers_mut().insert(SESSION_ID_HEADER, value);
+            }
+            res
+        }
+        Ok(Err(_)) if shutdown.is_cancelled() => {
+            AppError(StatusCode::SERVICE_UNAVAILABLE, "server is shutting down".to_string())
+                .into_response()
+        }
         Ok(Err(e)) => AppError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
         Err(e) => AppError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
     }
 }
 
+/// Returns the requested session id if it exists, otherwise creates a new one.
+fn resolve_session(sessions: &SessionManager, requested: Option<Uuid>) -> Result<Uuid> {
+    match requested {
+        Some(id) if sessions.exists(id)? => Ok(id),
+        _ => sessions.create_session(),
+    }
+}
+
+// Streaming chat endpoint
+//
+// Generation still happens on a blocking task, as in `chat`, but instead
+// of buffering the full reply we bridge each delta to the SSE stream over an
+// mpsc channel as soon as the upstream model produces it.
+async fn chat_stream(
+    State(state): State<Arc<AppState>>,
+    Json(request): Json<ChatRequest>,
+) -> Response {
+    let session_id = match resolve_session(&state.sessions, request.session_id) {
+        Ok(id) => id,
+        Err(e) => return AppError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    };
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<Result<Event, Infallible>>(32);
+
+    let config = state.config.clone();
+    let sessions = state.sessions.clone();
+    let shutdown = state.shutdown.clone();
+    let message = request.message;
+    let model = request.model;
+
+    tokio::task::spawn_blocking(move || {
+        let rt = tokio::runtime::Handle::current();
+        rt.block_on(async {
+            macro_rules! fail {
+                ($e:expr) => {{
+                    let _ = tx
+                        .send(Ok(Event::default().event("error").data($e.to_string())))
+                        .await;
+                    return;
+                }};
+            }
+
+            let memory = match MemoryManager::new(&config.memory) {
+                Ok(memory) => memory,
+                Err(e) => fail!(e),
+            };
+
+            let agent_config = AgentConfig {
+                model: model.unwrap_or_else(|| config.agent.default_model.clone()),
+                context_window: config.agent.context_window,
+                reserve_tokens: config.agent.reserve_tokens,
+            };
+
+            let mut agent = match Agent::new(agent_config, &config, memory).await {
+                Ok(agent) => agent,
+                Err(e) => fail!(e),
+            };
+
+            let history = match sessions.history(session_id) {
+                Ok(history) => history,
+                Err(e) => fail!(e),
+            };
+
+            let resume = if history.is_empty() {
+                agent.new_session().await
+            } else {
+                agent.resume_session(&history).await
+            };
+            if let Err(e) = resume {
+                fail!(e);
+            }
+
+            let model_name = agent.model().to_string();
+            let mut deltas = match agent.chat_stream(&message).await {
+                Ok(deltas) => deltas,
+                Err(e) => fail!(e),
+            };
+            let mut full_response = String::new();
+
+            loop {
+                let delta = tokio::select! {
+                    delta = deltas.next() => delta,
+                    _ = shutdown.cancelled() => {
+                        let _ = tx
+                            .send(Ok(Event::default().event("error").data("server is shutting down")))
+                            .await;
+                        return;
+                    }
+                };
+
+                let Some(delta) = delta else { break };
+
+                match delta {
+                    Ok(text) => {
+                        full_response.push_str(&text);
+                        if tx
+                            .send(Ok(Event::default().event("token").data(text)))
+                            .await
+                            .is_err()
+                        {
+                            // Client disconnected; stop driving the model.
+                            return;
+                        }
+                    }
+                    Err(e) => fail!(e),
+                }
+            }
+
+            if let Err(e) = sessions.append_exchange(session_id, &message, &full_response) {
+                fail!(e);
+            }
+
+            let _ = tx
+                .send(Ok(Event::default().event("done").data(model_name)))
+                .await;
+        })
+    });
+
+    let mut res = Sse::new(ReceiverStream::new(rx))
+        .keep_alive(KeepAlive::default())
+        .into_response();
+    if let Ok(value) = HeaderValue::from_str(&session_id.to_string()) {
+        res.headers_mut().insert(SESSION_ID_HEADER, value);
+    }
+    res
+}
+
 // Memory search endpoint
 #[derive(Deserialize)]
 struct SearchQuery {
@@ -239,3 +480,35 @@ fn memory_stats_inner(
         index_size_kb: stats.index_size_kb,
     })
 }
+
+// Session endpoints
+#[derive(Serialize)]
+struct SessionHistoryResponse {
+    session_id: Uuid,
+    turns: Vec<Turn>,
+}
+
+async fn list_sessions(State(state): State<Arc<AppState>>) -> Response {
+    match state.sessions.list_sessions() {
+        Ok(sessions) => Json(sessions).into_response(),
+        Err(e) => AppError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn get_session(State(state): State<Arc<AppState>>, Path(id): Path<Uuid>) -> Response {
+    match state.sessions.history(id) {
+        Ok(turns) => Json(SessionHistoryResponse {
+            session_id: id,
+            turns,
+        })
+        .into_response(),
+        Err(e) => AppError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
+
+async fn delete_session(State(state): State<Arc<AppState>>, Path(id): Path<Uuid>) -> Response {
+    match state.sessions.delete_session(id) {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => AppError(StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+    }
+}
@@ -0,0 +1,4 @@
+pub mod http;
+mod session;
+
+pub use http::Server;
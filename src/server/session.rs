@@ -0,0 +1,163 @@
+//! Durable HTTP session store.
+//!
+//! The chat handler spawns a fresh [`Agent`](crate::agent::Agent) per
+//! request, so session state can't simply live on the agent. Instead
+//! `SessionManager` owns its
+//! own connection, keyed by `session_id`, and hands plain conversation rows
+//! to whichever agent handles the next request for that session.
+
+use anyhow::Result;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Mutex;
+use uuid::Uuid;
+
+use crate::agent::Turn;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub session_id: Uuid,
+    pub created_at: String,
+    pub turn_count: usize,
+}
+
+pub struct SessionManager {
+    conn: Mutex<Connection>,
+}
+
+impl SessionManager {
+    pub fn new(db_path: impl AsRef<Path>) -> Result<Self> {
+        let conn = Connection::open(db_path)?;
+        // rusqlite/SQLite leave FK enforcement off by default, which would
+        // silently defeat session_turns' ON DELETE CASCADE below.
+        conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+        conn.execute_batch(
+            "CREATE TABLE IF NOT EXISTS sessions (
+                id TEXT PRIMARY KEY,
+                created_at TEXT NOT NULL DEFAULT (datetime('now'))
+            );
+            CREATE TABLE IF NOT EXISTS session_turns (
+                session_id TEXT NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+                seq INTEGER NOT NULL,
+                role TEXT NOT NULL,
+                content TEXT NOT NULL,
+                PRIMARY KEY (session_id, seq)
+            );",
+        )?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    /// Create a new session and return its id.
+    pub fn create_session(&self) -> Result<Uuid> {
+        let id = Uuid::new_v4();
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "INSERT INTO sessions (id) VALUES (?1)",
+            params![id.to_string()],
+        )?;
+        Ok(id)
+    }
+
+    /// Whether a session with this id exists.
+    pub fn exists(&self, session_id: Uuid) -> Result<bool> {
+        let conn = self.conn.lock().unwrap();
+        let count: i64 = conn.query_row(
+            "SELECT COUNT(*) FROM sessions WHERE id = ?1",
+            params![session_id.to_string()],
+            |row| row.get(0),
+        )?;
+        Ok(count > 0)
+    }
+
+    /// Load a session's history in order, oldest first.
+    pub fn history(&self, session_id: Uuid) -> Result<Vec<Turn>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT role, content FROM session_turns WHERE session_id = ?1 ORDER BY seq ASC",
+        )?;
+        let turns = stmt
+            .query_map(params![session_id.to_string()], |row| {
+                Ok(Turn {
+                    role: row.get(0)?,
+                    content: row.get(1)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(turns)
+    }
+
+    /// Append a user message and the assistant's reply to a session.
+    pub fn append_exchange(&self, session_id: Uuid, message: &str, response: &str) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        let next_seq: i64 = conn.query_row(
+            "SELECT COALESCE(MAX(seq), -1) + 1 FROM session_turns WHERE session_id = ?1",
+            params![session_id.to_string()],
+            |row| row.get(0),
+        )?;
+
+        conn.execute(
+            "INSERT INTO session_turns (session_id, seq, role, content) VALUES (?1, ?2, 'user', ?3)",
+            params![session_id.to_string(), next_seq, message],
+        )?;
+        conn.execute(
+            "INSERT INTO session_turns (session_id, seq, role, content) VALUES (?1, ?2, 'assistant', ?3)",
+            params![session_id.to_string(), next_seq + 1, response],
+        )?;
+
+        Ok(())
+    }
+
+    /// List all known sessions, newest first.
+    pub fn list_sessions(&self) -> Result<Vec<SessionSummary>> {
+        let conn = self.conn.lock().unwrap();
+        let mut stmt = conn.prepare(
+            "SELECT s.id, s.created_at, COUNT(t.seq)
+             FROM sessions s
+             LEFT JOIN session_turns t ON t.session_id = s.id
+             GROUP BY s.id
+             ORDER BY s.created_at DESC",
+        )?;
+        let sessions = stmt
+            .query_map([], |row| {
+                let id: String = row.get(0)?;
+                Ok(SessionSummary {
+                    session_id: id.parse().unwrap_or_default(),
+                    created_at: row.get(1)?,
+                    turn_count: row.get::<_, i64>(2)? as usize,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(sessions)
+    }
+
+    /// Delete a session and its history.
+    pub fn delete_session(&self, session_id: Uuid) -> Result<()> {
+        let conn = self.conn.lock().unwrap();
+        conn.execute(
+            "DELETE FROM sessions WHERE id = ?1",
+            params![session_id.to_string()],
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn delete_session_cascades_to_its_history() {
+        let sessions = SessionManager::new(":memory:").unwrap();
+        let id = sessions.create_session().unwrap();
+        sessions.append_exchange(id, "hi", "hello").unwrap();
+        assert_eq!(sessions.history(id).unwrap().len(), 2);
+
+        sessions.delete_session(id).unwrap();
+
+        assert!(!sessions.exists(id).unwrap());
+        assert!(sessions.history(id).unwrap().is_empty());
+    }
+}